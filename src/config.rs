@@ -0,0 +1,60 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfigFile {
+    pub layout: LayoutConfig,
+
+    /// Path to a `.ttf`/`.otf` file to load the rendering typeface from.
+    /// Checked before `font_family`; falls back to the bundled font if unset
+    /// or if the file can't be loaded.
+    #[serde(default)]
+    pub font_path: Option<String>,
+
+    /// Name of a system font family to render with, e.g. `"Arial"`. Checked
+    /// after `font_path` but before the bundled fallback.
+    #[serde(default)]
+    pub font_family: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LayoutConfig {
+    pub width: i32,
+    pub height: i32,
+    #[serde(default)]
+    pub rotation: DisplayRotation,
+    pub columns: Vec<ColumnConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ColumnConfig {
+    /// This column's share of `layout.width`, relative to the other
+    /// columns' `width`s. E.g. two columns both set to `1.0` split the
+    /// board evenly in half; `[2.0, 1.0, 1.0]` gives the first column half
+    /// the total width and the other two a quarter each.
+    pub width: f32,
+    pub sections: Vec<SectionConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SectionConfig {
+    pub agency: String,
+    pub direction: String,
+}
+
+/// How the rendered bitmap should be rotated before being handed to the
+/// display. Real Kindle models mount their panel differently, so this is
+/// driven by config rather than hardcoded to a single `RenderTarget`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayRotation {
+    Rotate0,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+impl Default for DisplayRotation {
+    fn default() -> Self {
+        DisplayRotation::Rotate0
+    }
+}
@@ -2,25 +2,112 @@ use std::collections::HashMap;
 
 use crate::{
     api_client::{Line, Upcoming},
-    config::{ConfigFile, SectionConfig},
+    config::{ColumnConfig, ConfigFile, DisplayRotation, SectionConfig},
 };
 use eyre::{bail, eyre, Result};
 use itertools::Itertools;
 use skia_safe::{
-    Bitmap, Canvas, Color4f, Font, FontStyle, ImageInfo, Paint, Point, TextBlob, Typeface,
+    Bitmap, Canvas, Color4f, Font, FontStyle, ImageInfo, Paint, Point, Rect, TextBlob, Typeface,
 };
 use tracing::warn;
 
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub enum RenderTarget {
-    Kindle,
-    Other,
+/// A translation applied to everything drawn through a `Renderer`.
+#[derive(Clone, Copy, Debug, Default)]
+struct Transform {
+    dx: f32,
+    dy: f32,
+}
+
+/// Wraps a `Canvas` with a stack of `Transform`s, so each region of the
+/// layout (a column, a section, an offscreen panel) can be drawn starting
+/// at its own local `(0, 0)` instead of every draw call threading explicit
+/// pixel offsets through the whole call chain.
+struct Renderer<'a> {
+    canvas: &'a mut Canvas,
+    stack: Vec<Transform>,
+}
+
+impl<'a> Renderer<'a> {
+    fn new(canvas: &'a mut Canvas) -> Self {
+        Renderer {
+            canvas,
+            stack: vec![Transform::default()],
+        }
+    }
+
+    fn transform(&self) -> Transform {
+        *self.stack.last().expect("transform stack is never empty")
+    }
+
+    fn absolute(&self, (x, y): (i32, i32)) -> (i32, i32) {
+        let t = self.transform();
+        (x + t.dx as i32, y + t.dy as i32)
+    }
+
+    /// Pushes a translation by `(dx, dy)` relative to the current transform,
+    /// runs `f` in that coordinate space, then restores the parent
+    /// transform.
+    fn save_translated(
+        &mut self,
+        dx: f32,
+        dy: f32,
+        f: impl FnOnce(&mut Renderer) -> Result<()>,
+    ) -> Result<()> {
+        let parent = self.transform();
+        self.stack.push(Transform {
+            dx: parent.dx + dx,
+            dy: parent.dy + dy,
+        });
+        let result = f(self);
+        self.stack.pop();
+        result
+    }
+
+    fn draw_line(&mut self, from: (i32, i32), to: (i32, i32), paint: &Paint) {
+        self.canvas
+            .draw_line(self.absolute(from), self.absolute(to), paint);
+    }
+
+    fn draw_oval(&mut self, rect: Rect, paint: &Paint) {
+        let t = self.transform();
+        self.canvas.draw_oval(rect.with_offset((t.dx, t.dy)), paint);
+    }
+
+    fn draw_rect(&mut self, rect: Rect, paint: &Paint) {
+        let t = self.transform();
+        self.canvas.draw_rect(rect.with_offset((t.dx, t.dy)), paint);
+    }
+
+    fn draw_text_blob(
+        &mut self,
+        blob: impl AsRef<TextBlob>,
+        origin: (i32, i32),
+        paint: &Paint,
+    ) {
+        self.canvas
+            .draw_text_blob(blob, self.absolute(origin), paint);
+    }
+}
+
+/// Splits `total_width` into pixel ranges for `columns`, proportional to
+/// each column's relative `width`.
+fn column_bounds(columns: &[ColumnConfig], total_width: i32) -> Vec<(i32, i32)> {
+    let total_weight: f32 = columns.iter().map(|c| c.width).sum();
+
+    let mut bounds = Vec::with_capacity(columns.len());
+    let mut x = 0.0;
+    for column in columns {
+        let start = x;
+        x += total_width as f32 * column.width / total_weight;
+        bounds.push((start.round() as i32, x.round() as i32));
+    }
+
+    bounds
 }
 
 fn render_ctx(
-    render_target: RenderTarget,
     config_file: &ConfigFile,
-    closure: impl FnOnce(&mut Canvas) -> Result<()>,
+    closure: impl FnOnce(&mut Renderer) -> Result<()>,
 ) -> Result<Vec<u8>> {
     let mut bitmap = Bitmap::new();
     if !bitmap.set_info(
@@ -41,35 +128,35 @@ fn render_ctx(
 
     canvas.clear(Color4f::new(1.0, 1.0, 1.0, 1.0));
 
-    closure(&mut canvas)?;
-
-    let image = bitmap.as_image();
-
-    let final_image = if render_target == RenderTarget::Kindle {
-        let mut rotated_bitmap = Bitmap::new();
-        if !rotated_bitmap.set_info(
-            &ImageInfo::new(
-                (config_file.layout.height, config_file.layout.width),
-                skia_safe::ColorType::Gray8,
-                skia_safe::AlphaType::Unknown,
-                None,
-            ),
-            None,
-        ) {
-            bail!("failed to initialize skia bitmap");
-        }
-        rotated_bitmap.alloc_pixels();
+    let mut renderer = Renderer::new(&mut canvas);
 
-        let mut rotated_canvas = Canvas::from_bitmap(&rotated_bitmap, None)
-            .ok_or(eyre!("failed to construct skia canvas"))?;
+    closure(&mut renderer)?;
 
-        rotated_canvas.translate(Point::new(config_file.layout.height as f32, 0.0));
-        rotated_canvas.rotate(90.0, Some(Point::new(0.0, 0.0)));
-        rotated_canvas.draw_image(image, (0, 0), None);
+    let image = bitmap.as_image();
 
-        rotated_bitmap.as_image()
-    } else {
-        image
+    let width = config_file.layout.width;
+    let height = config_file.layout.height;
+
+    let final_image = match config_file.layout.rotation {
+        DisplayRotation::Rotate0 => image,
+        DisplayRotation::Rotate90 => rotated_image(
+            (height, width),
+            Point::new(height as f32, 0.0),
+            90.0,
+            &image,
+        )?,
+        DisplayRotation::Rotate180 => rotated_image(
+            (width, height),
+            Point::new(width as f32, height as f32),
+            180.0,
+            &image,
+        )?,
+        DisplayRotation::Rotate270 => rotated_image(
+            (height, width),
+            Point::new(0.0, width as f32),
+            270.0,
+            &image,
+        )?,
     };
 
     let image_data = final_image
@@ -79,22 +166,109 @@ fn render_ctx(
     Ok(image_data.as_bytes().into())
 }
 
+/// Draws `image` into a fresh bitmap of size `(width, height)` after
+/// translating by `translate` and rotating by `angle_degrees` about the
+/// origin, returning the result as a new skia image.
+fn rotated_image(
+    (width, height): (i32, i32),
+    translate: Point,
+    angle_degrees: f32,
+    image: &skia_safe::Image,
+) -> Result<skia_safe::Image> {
+    let mut bitmap = Bitmap::new();
+    if !bitmap.set_info(
+        &ImageInfo::new(
+            (width, height),
+            skia_safe::ColorType::Gray8,
+            skia_safe::AlphaType::Unknown,
+            None,
+        ),
+        None,
+    ) {
+        bail!("failed to initialize skia bitmap");
+    }
+    bitmap.alloc_pixels();
+
+    let mut canvas =
+        Canvas::from_bitmap(&bitmap, None).ok_or(eyre!("failed to construct skia canvas"))?;
+
+    canvas.translate(translate);
+    canvas.rotate(angle_degrees, Some(Point::new(0.0, 0.0)));
+    canvas.draw_image(image, (0, 0), None);
+
+    Ok(bitmap.as_image())
+}
+
+/// A fallback font bundled into the binary so rendering never fails merely
+/// because a named system font (e.g. "arial") isn't installed.
+static FALLBACK_FONT_DATA: &[u8] = include_bytes!("../assets/fonts/DejaVuSans.ttf");
+
+/// Loads a typeface, preferring `config_file.font_path`, then
+/// `config_file.font_family`, then the embedded fallback font.
+fn load_typeface(config_file: &ConfigFile, style: FontStyle) -> Result<Typeface> {
+    if let Some(path) = &config_file.font_path {
+        match std::fs::read(path) {
+            Ok(bytes) => {
+                if let Some(typeface) = Typeface::from_data(skia_safe::Data::new_copy(&bytes), None)
+                {
+                    return Ok(typeface);
+                }
+                warn!(font_path = path, "failed to parse configured font_path, falling back");
+            }
+            Err(err) => {
+                warn!(font_path = path, %err, "failed to read configured font_path, falling back");
+            }
+        }
+    }
+
+    if let Some(family) = &config_file.font_family {
+        if let Some(typeface) = Typeface::new(family, style) {
+            return Ok(typeface);
+        }
+        warn!(font_family = family, "failed to load configured font_family, falling back");
+    }
+
+    Typeface::from_data(skia_safe::Data::new_copy(FALLBACK_FONT_DATA), None)
+        .ok_or(eyre!("failed to construct embedded fallback typeface"))
+}
+
+/// Truncates `text` with a trailing ellipsis so its measured advance width
+/// fits within `max_width`, returning it unchanged if it already fits.
+fn truncate_to_width(font: &Font, text: &str, max_width: f32) -> String {
+    if max_width <= 0.0 {
+        return String::new();
+    }
+
+    if font.measure_str(text, None).0 <= max_width {
+        return text.to_string();
+    }
+
+    let mut chars: Vec<char> = text.chars().collect();
+    while !chars.is_empty() {
+        chars.pop();
+        let candidate: String = chars.iter().collect::<String>() + "…";
+        if font.measure_str(&candidate, None).0 <= max_width {
+            return candidate;
+        }
+    }
+
+    "…".to_string()
+}
+
 pub fn stops_png(
-    render_target: RenderTarget,
     stop_data: HashMap<String, HashMap<String, Vec<(Line, Vec<Upcoming>)>>>,
     config_file: &ConfigFile,
 ) -> Result<Vec<u8>> {
     let black_paint = Paint::new(Color4f::new(0.0, 0.0, 0.0, 1.0), None);
     let grey_paint = Paint::new(Color4f::new(0.6, 0.6, 0.6, 1.0), None);
 
-    let typeface = Typeface::new("arial", FontStyle::bold())
-        .ok_or(eyre!("failed to construct skia typeface"))?;
+    let typeface = load_typeface(config_file, FontStyle::bold())?;
 
     let font = Font::new(typeface, 24.0);
 
-    let draw_data = |canvas: &mut Canvas,
+    let draw_data = |renderer: &mut Renderer,
                      section: &SectionConfig,
-                     (x1, x2): (i32, i32),
+                     column_width: i32,
                      y: &mut i32|
      -> Result<()> {
         let agency = match stop_data.get(&section.agency) {
@@ -117,68 +291,84 @@ pub fn stops_png(
             }
         };
 
-        if x1 > 0 {
-            canvas.draw_line((x1, 0), (x1, config_file.layout.height), &black_paint);
-        }
-
         let lines_len = lines.len();
 
+        let time_column_padding = 10;
+
         for (idx, (line, upcoming)) in lines.into_iter().enumerate() {
-            let x = x1 + 20;
+            let x = 20;
 
             let line_name_blob = TextBlob::new(&line.line, &font)
                 .ok_or(eyre!("failed to construct skia text blob"))?;
 
-            let line_name_bounds = line_name_blob.bounds();
+            let line_name_oval = line_name_blob.bounds().with_offset((x, *y));
+            let (line_name_width, _) = font.measure_str(&line.line, None);
 
-            let line_name_oval = line_name_bounds.with_offset((x, *y));
+            renderer.draw_oval(line_name_oval, &grey_paint);
 
-            canvas.draw_oval(line_name_oval, &grey_paint);
+            renderer.draw_text_blob(&line_name_blob, (x, *y), &black_paint);
 
-            canvas.draw_text_blob(&line_name_blob, (x, *y), &black_paint);
+            let mins = upcoming.into_iter().map(|t| t.minutes()).join(", ");
+            let time_text = format!("{mins} min");
+            let (time_width, _) = font.measure_str(&time_text, None);
 
-            let destination_blob = TextBlob::new(&line.destination, &font)
-                .ok_or(eyre!("failed to construct skia text blob"))?;
-            canvas.draw_text_blob(
-                destination_blob,
-                ((x + line_name_bounds.width() as i32), *y),
-                &black_paint,
+            let destination_x = x + line_name_width as i32;
+            let time_column_start = column_width - time_width as i32 - time_column_padding;
+
+            let destination_text = truncate_to_width(
+                &font,
+                &line.destination,
+                (time_column_start - destination_x) as f32,
             );
 
-            let mins = upcoming.into_iter().map(|t| t.minutes()).join(", ");
-            let time_text = format!("{mins} min");
+            let destination_blob = TextBlob::new(&destination_text, &font)
+                .ok_or(eyre!("failed to construct skia text blob"))?;
+            renderer.draw_text_blob(destination_blob, (destination_x, *y), &black_paint);
 
             let time_blob = TextBlob::new(time_text, &font)
                 .ok_or(eyre!("failed to construct skia text blob"))?;
 
-            let x = x2 - time_blob.bounds().width() as i32;
-            canvas.draw_text_blob(time_blob, (x, *y), &black_paint);
+            let time_x = column_width - time_width as i32;
+            renderer.draw_text_blob(time_blob, (time_x, *y), &black_paint);
 
             if idx < (lines_len - 1) {
-                canvas.draw_line((x1 + 40, *y + 15), (x2 - 40, *y + 15), &grey_paint);
+                renderer.draw_line((40, *y + 15), (column_width - 40, *y + 15), &grey_paint);
                 *y += 40;
             } else {
                 *y += 15;
             }
         }
 
-        canvas.draw_line((x1, *y), (x2, *y), &black_paint);
+        renderer.draw_line((0, *y), (column_width, *y), &black_paint);
         *y += 28;
 
         Ok(())
     };
 
-    let halfway = config_file.layout.width / 2;
-
-    let image_data = render_ctx(render_target, config_file, |canvas| {
-        let mut y = 38;
-        for section in &config_file.layout.left.sections {
-            draw_data(canvas, section, (0, halfway), &mut y)?;
-        }
-
-        let mut y = 38;
-        for section in &config_file.layout.right.sections {
-            draw_data(canvas, section, (halfway, config_file.layout.width), &mut y)?;
+    let bounds = column_bounds(&config_file.layout.columns, config_file.layout.width);
+
+    let image_data = render_ctx(config_file, |renderer| {
+        for (idx, (column, (x1, x2))) in config_file
+            .layout
+            .columns
+            .iter()
+            .zip(&bounds)
+            .enumerate()
+        {
+            let column_width = x2 - x1;
+
+            renderer.save_translated(*x1 as f32, 0.0, |renderer| {
+                if idx > 0 {
+                    renderer.draw_line((0, 0), (0, config_file.layout.height), &black_paint);
+                }
+
+                let mut y = 38;
+                for section in &column.sections {
+                    draw_data(renderer, section, column_width, &mut y)?;
+                }
+
+                Ok(())
+            })?;
         }
 
         Ok(())
@@ -187,33 +377,111 @@ pub fn stops_png(
     Ok(image_data)
 }
 
+/// How a rendering failure should be presented: as an e-ink-friendly PNG, or
+/// as a dense plain-text block for logging / LLM consumption.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RenderMode {
+    Image,
+    Compact,
+}
+
 pub fn error_png(
-    render_target: RenderTarget,
+    render_mode: RenderMode,
     config_file: &ConfigFile,
     error: eyre::Report,
 ) -> Result<Vec<u8>> {
+    match render_mode {
+        RenderMode::Compact => Ok(error_compact(&error).into_bytes()),
+        RenderMode::Image => draw_error_image(config_file, error),
+    }
+}
+
+/// Renders the error chain as a dense, aligned text block, one cause per
+/// line, each indented one level deeper than its parent.
+fn error_compact(error: &eyre::Report) -> String {
+    error
+        .chain()
+        .enumerate()
+        .map(|(idx, cause)| format!("{}{cause}", "  ".repeat(idx)))
+        .join("\n")
+}
+
+fn draw_error_image(config_file: &ConfigFile, error: eyre::Report) -> Result<Vec<u8>> {
     let black_paint = Paint::new(Color4f::new(0.0, 0.0, 0.0, 1.0), None);
+    let white_paint = Paint::new(Color4f::new(1.0, 1.0, 1.0, 1.0), None);
 
-    let typeface = Typeface::new("arial", FontStyle::normal())
-        .ok_or(eyre!("failed to construct skia typeface"))?;
+    let typeface = load_typeface(config_file, FontStyle::normal())?;
 
     let big_font = Font::new(&typeface, 36.0);
-    let small_font: skia_safe::Handle<_> = Font::new(typeface, 12.0);
+    let small_font: skia_safe::Handle<_> = Font::new(typeface, 18.0);
+
+    let width = config_file.layout.width;
+    let band_height = 80;
+    let text_padding = 20;
+    let line_height = 24;
+    let wrap_width = (width - text_padding * 2) as f32;
+
+    let (heading_width, _) = big_font.measure_str("ERROR", None);
+    let heading_x = ((width as f32 - heading_width) / 2.0) as i32;
 
     let failure_blob =
         TextBlob::new("ERROR", &big_font).ok_or(eyre!("failed to construct skia text blob"))?;
 
-    let data = render_ctx(render_target, config_file, move |canvas| {
-        canvas.draw_text_blob(failure_blob, (100, 200), &black_paint);
-        let mut y = 250;
+    let data = render_ctx(config_file, move |renderer| {
+        renderer.draw_rect(
+            Rect::from_xywh(0.0, 0.0, width as f32, band_height as f32),
+            &black_paint,
+        );
+        renderer.draw_text_blob(&failure_blob, (heading_x, 54), &white_paint);
+
+        let mut y = band_height + 40;
         for e in error.chain() {
-            let error_blob = TextBlob::new(format!("{e}"), &small_font)
-                .ok_or(eyre!("failed to construct skia text blob"))?;
-            canvas.draw_text_blob(error_blob, (100, y), &black_paint);
-            y += 20;
+            for line in wrap_text(&small_font, &format!("{e}"), wrap_width) {
+                let error_blob = TextBlob::new(&line, &small_font)
+                    .ok_or(eyre!("failed to construct skia text blob"))?;
+                renderer.draw_text_blob(error_blob, (text_padding, y), &black_paint);
+                y += line_height;
+            }
         }
         Ok(())
     })?;
 
     Ok(data)
 }
+
+/// Greedily wraps `text` at word boundaries so each returned line's measured
+/// advance width fits within `max_width`.
+fn wrap_text(font: &Font, text: &str, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let (word_width, _) = font.measure_str(word, None);
+        if word_width > max_width {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            lines.push(truncate_to_width(font, word, max_width));
+            continue;
+        }
+
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{current} {word}")
+        };
+
+        if current.is_empty() || font.measure_str(&candidate, None).0 <= max_width {
+            current = candidate;
+        } else {
+            lines.push(current);
+            current = word.to_string();
+        }
+    }
+
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    lines
+}